@@ -29,6 +29,20 @@ pub trait ConsumableEventApp {
   /// second. One system would write customer events at a random rate, and
   /// another system would consume an event whenever a timer goes off.
   fn add_persistent_consumable_event<E: Event>(&mut self) -> &mut Self;
+
+  /// Adds a "persistent" consumable event type of `E`, the same as
+  /// [`add_persistent_consumable_event`](Self::add_persistent_consumable_event),
+  /// except unconsumed events are also dropped once they are more than
+  /// `max_age` frames old. This gives the unbounded growth persistent events
+  /// are prone to a safety valve.
+  ///
+  /// An example for when to use this is the customer line above, but where a
+  /// customer gives up and leaves if they wait more than `max_age` frames
+  /// without being served.
+  fn add_persistent_consumable_event_with_max_age<E: Event>(
+    &mut self,
+    max_age: u64,
+  ) -> &mut Self;
 }
 
 impl ConsumableEventApp for App {
@@ -43,6 +57,15 @@ impl ConsumableEventApp for App {
       .init_resource::<ConsumableEvents<E>>()
       .add_systems(First, clear_consumed_events::<E>)
   }
+
+  fn add_persistent_consumable_event_with_max_age<E: Event>(
+    &mut self,
+    max_age: u64,
+  ) -> &mut Self {
+    self
+      .init_resource::<ConsumableEvents<E>>()
+      .add_systems(First, age_out_events::<E>(max_age))
+  }
 }
 
 impl ConsumableEventApp for SubApp {
@@ -57,6 +80,15 @@ impl ConsumableEventApp for SubApp {
       .init_resource::<ConsumableEvents<E>>()
       .add_systems(First, clear_consumed_events::<E>)
   }
+
+  fn add_persistent_consumable_event_with_max_age<E: Event>(
+    &mut self,
+    max_age: u64,
+  ) -> &mut Self {
+    self
+      .init_resource::<ConsumableEvents<E>>()
+      .add_systems(First, age_out_events::<E>(max_age))
+  }
 }
 
 /// A system for clearing all events of type `E`.
@@ -69,6 +101,16 @@ fn clear_consumed_events<E: Event>(mut events: ResMut<ConsumableEvents<E>>) {
   events.clear_consumed();
 }
 
+/// Builds a system that clears consumed events of type `E`, and additionally
+/// discards unconsumed events older than `max_age` frames.
+fn age_out_events<E: Event>(
+  max_age: u64,
+) -> impl Fn(ResMut<ConsumableEvents<E>>) {
+  move |mut events: ResMut<ConsumableEvents<E>>| {
+    events.age_out(max_age);
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use crate::*;
@@ -160,4 +202,39 @@ mod tests {
     // consumed/halved.
     assert_eq!(values, [0, 1, 2, 0, 1, 2, 3, 4]);
   }
+
+  #[test]
+  fn add_persistent_consumable_event_with_max_age() {
+    let mut app = App::empty();
+    app.main_mut().update_schedule = Some(Main.intern());
+    app
+      .add_persistent_consumable_event_with_max_age::<TestEvent>(2)
+      .add_systems(Main, run_first_schedule);
+
+    app
+      .world_mut()
+      .resource_mut::<ConsumableEvents<TestEvent>>()
+      .send(TestEvent { value: 0 });
+
+    // The event is still within `max_age` frames of being sent, so it
+    // survives even though nothing has consumed it.
+    app.update();
+    assert_eq!(
+      app.world_mut().resource_mut::<ConsumableEvents<TestEvent>>().read().count(),
+      1
+    );
+    app.update();
+    assert_eq!(
+      app.world_mut().resource_mut::<ConsumableEvents<TestEvent>>().read().count(),
+      1
+    );
+
+    // Once the event is older than `max_age` frames, it is dropped even
+    // though it was never consumed.
+    app.update();
+    assert_eq!(
+      app.world_mut().resource_mut::<ConsumableEvents<TestEvent>>().read().count(),
+      0
+    );
+  }
 }
@@ -1,13 +1,14 @@
 #![doc = include_str!("../README.md")]
 
 use std::{
+  marker::PhantomData,
   ops::{Deref, DerefMut},
   slice::IterMut,
 };
 
 use bevy_ecs::{
   event::Event,
-  system::{ResMut, Resource, SystemParam},
+  system::{Local, Res, ResMut, Resource, SystemParam},
 };
 
 #[cfg(feature = "bevy_app")]
@@ -67,15 +68,26 @@ pub use app::ConsumableEventApp;
 /// ```
 #[derive(Resource)]
 pub struct ConsumableEvents<E: Event> {
-  /// The events in the buffer. `None` implies that the event there was
-  /// consumed. `Some` means that the event has not been consumed yet.
-  events: Vec<Option<E>>,
+  /// The events in the buffer, in the order they were sent. `event` being
+  /// `None` implies that the event there was consumed. `Some` means that the
+  /// event has not been consumed yet.
+  events: Vec<EventInstance<E>>,
+  /// The id that will be assigned to the next event sent. Only ever
+  /// increases, even across [`ConsumableEvents::clear`], so ids remain valid
+  /// for comparison no matter how the buffer has been cleared or compacted.
+  event_count: u64,
+  /// The current frame tick, stamped onto newly sent events. Only advanced by
+  /// [`ConsumableEvents::age_out`], which the `First`-schedule system
+  /// installed by
+  /// [`add_persistent_consumable_event_with_max_age`](crate::ConsumableEventApp::add_persistent_consumable_event_with_max_age)
+  /// calls once per frame.
+  frame: u64,
 }
 
 // Derived Default impl would incorrectly require E: Default
 impl<E: Event> Default for ConsumableEvents<E> {
   fn default() -> Self {
-    Self { events: Default::default() }
+    Self { events: Default::default(), event_count: 0, frame: 0 }
   }
 }
 
@@ -83,7 +95,15 @@ impl<E: Event> ConsumableEvents<E> {
   /// "Sends" `event` by writing it to the buffer. [`read`] can then read the
   /// event.
   pub fn send(&mut self, event: E) {
-    self.events.push(Some(event));
+    let id = self.next_id();
+    #[cfg(feature = "trace")]
+    let _span = tracing::trace_span!(
+      "consumable_event_send",
+      event = std::any::type_name::<E>(),
+      id = id.id
+    )
+    .entered();
+    self.events.push(EventInstance { id, frame: self.frame, event: Some(event) });
   }
 
   /// Sends a list of `events` all at once, which can later be [`read`]. This is
@@ -106,7 +126,30 @@ impl<E: Event> ConsumableEvents<E> {
     ConsumableEventIterator { iter: self.events.iter_mut() }
   }
 
+  /// Reads the unconsumed events stored in self, also yielding the
+  /// [`EventId`] each event was sent with. Useful for tracing which events a
+  /// system consumed, e.g. to reconstruct a timeline of consume order.
+  pub fn read_with_id(
+    &mut self,
+  ) -> impl Iterator<Item = (Consume<E>, EventId<E>)> {
+    self.read().map(|event| {
+      let id = event.id();
+      (event, id)
+    })
+  }
+
+  /// Returns an iterator over the unconsumed events stored in self, without
+  /// the ability to consume them. Unlike [`read`], this only needs a shared
+  /// borrow of self, so it is safe for multiple read-only observers (UI
+  /// highlighting, logging, analytics) to peek the same events in parallel.
+  pub fn peek(&self) -> impl Iterator<Item = &E> {
+    self.events.iter().filter_map(|instance| instance.event.as_ref())
+  }
+
   /// Clears all events stored in self. Unconsumed events are also dropped.
+  ///
+  /// This does not reset the id counter, so events sent after a `clear` will
+  /// still receive ids greater than any event sent before it.
   pub fn clear(&mut self) {
     self.events.clear();
   }
@@ -115,7 +158,26 @@ impl<E: Event> ConsumableEvents<E> {
   /// but calling it regularly will reduce memory usage (since the consumed
   /// events cannot be read anyway).
   pub fn clear_consumed(&mut self) {
-    self.events.retain(|event| event.is_some());
+    self.events.retain(|instance| instance.event.is_some());
+  }
+
+  /// Advances the frame tick stamped onto newly sent events by one, then
+  /// clears consumed events and any unconsumed event whose frame tick is more
+  /// than `max_age` frames old. Used to implement
+  /// [`add_persistent_consumable_event_with_max_age`](crate::ConsumableEventApp::add_persistent_consumable_event_with_max_age).
+  pub(crate) fn age_out(&mut self, max_age: u64) {
+    self.frame += 1;
+    let frame = self.frame;
+    self.events.retain(|instance| {
+      instance.event.is_some() && frame - instance.frame <= max_age
+    });
+  }
+
+  /// Assigns the next [`EventId`] and advances the counter.
+  fn next_id(&mut self) -> EventId<E> {
+    let id = EventId { id: self.event_count, _marker: PhantomData };
+    self.event_count += 1;
+    id
   }
 }
 
@@ -124,12 +186,84 @@ impl<E: Event> Extend<E> for ConsumableEvents<E> {
   where
     I: IntoIterator<Item = E>,
   {
-    self.events.extend(iter.into_iter().map(|event| Some(event)));
+    let iter = iter.into_iter();
+    self.events.reserve(iter.size_hint().0);
+    for event in iter {
+      let id = self.next_id();
+      self.events.push(EventInstance { id, frame: self.frame, event: Some(event) });
+    }
+  }
+}
+
+/// A single event stored in a [`ConsumableEvents`] buffer, tagged with the
+/// [`EventId`] it was sent with.
+#[derive(Debug)]
+struct EventInstance<E> {
+  /// The id this event was assigned at `send` time.
+  id: EventId<E>,
+  /// The frame tick this event was sent on. See
+  /// [`ConsumableEvents::age_out`].
+  frame: u64,
+  /// The event itself, or `None` if it has been consumed.
+  event: Option<E>,
+}
+
+/// A unique identifier for an event stored in a [`ConsumableEvents`] buffer.
+///
+/// Ids are assigned in the order events are sent (via [`ConsumableEvents::send`]
+/// or [`ConsumableEvents::send_batch`]) and are never reused, even across
+/// [`ConsumableEvents::clear`]. This means ids can be compared to determine
+/// relative send order without needing to look at where an event currently
+/// sits in the buffer.
+pub struct EventId<E> {
+  id: u64,
+  _marker: PhantomData<E>,
+}
+
+impl<E> Copy for EventId<E> {}
+
+impl<E> Clone for EventId<E> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<E> PartialEq for EventId<E> {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id
+  }
+}
+
+impl<E> Eq for EventId<E> {}
+
+impl<E> PartialOrd for EventId<E> {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<E> Ord for EventId<E> {
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    self.id.cmp(&other.id)
+  }
+}
+
+impl<E> std::hash::Hash for EventId<E> {
+  fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    self.id.hash(state);
+  }
+}
+
+impl<E> std::fmt::Debug for EventId<E> {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "event<{}>#{}", std::any::type_name::<E>(), self.id)
   }
 }
 
 /// Mutable borrow of a consumable event.
 pub struct Consume<'events, E> {
+  /// The id the event was sent with.
+  id: EventId<E>,
   /// The event itself.
   event: &'events mut Option<E>,
 }
@@ -149,8 +283,20 @@ impl<'events, E> DerefMut for Consume<'events, E> {
 }
 
 impl<'events, E> Consume<'events, E> {
+  /// Returns the id the event was sent with.
+  pub fn id(&self) -> EventId<E> {
+    self.id
+  }
+
   /// Consumes the event.
   pub fn consume(self) -> E {
+    #[cfg(feature = "trace")]
+    let _span = tracing::trace_span!(
+      "consumable_event_consume",
+      event = std::any::type_name::<E>(),
+      id = self.id.id
+    )
+    .entered();
     self.event.take().expect("The event has not been consumed until now.")
   }
 }
@@ -235,6 +381,14 @@ impl<'w, E: Event> ConsumableEventReader<'w, E> {
     self.events.read()
   }
 
+  /// Reads the unconsumed events, also yielding the [`EventId`] each event
+  /// was sent with. See [`ConsumableEvents::read_with_id`].
+  pub fn read_with_id(
+    &mut self,
+  ) -> impl Iterator<Item = (Consume<E>, EventId<E>)> {
+    self.events.read_with_id()
+  }
+
   /// Reads all unconsumed events, consuming them all along the way.
   pub fn read_and_consume_all(&mut self) -> impl Iterator<Item = E> + '_ {
     // TODO: The lifetime bounds of this function are wrong. Rust 2024 edition
@@ -243,23 +397,189 @@ impl<'w, E: Event> ConsumableEventReader<'w, E> {
   }
 }
 
+/// Peeks at (but cannot consume) events of type `E`.
+///
+/// Unlike [`ConsumableEventReader`] and [`ConsumableEventWriter`], which take
+/// a [`ResMut`] and so serialize with every other system touching `E`, a
+/// `ConsumableEventPeeker` only takes a [`Res`]. This lets multiple read-only
+/// observers of an event type (UI highlighting, logging, analytics) run in
+/// parallel with each other, reserving `ResMut` for systems that actually
+/// consume events.
+///
+/// # Usage
+///
+/// `ConsumableEventPeeker`s are usually declared as a [`SystemParam`].
+/// ```
+/// use bevy_ecs::prelude::*;
+/// use bevy_consumable_event::ConsumableEventPeeker;
+///
+/// #[derive(Event, Debug)]
+/// pub struct MyEvent; // Custom event type.
+///
+/// fn my_system(peeker: ConsumableEventPeeker<MyEvent>) {
+///   for event in peeker.peek() {
+///     println!("{:?}", event);
+///   }
+/// }
+///
+/// bevy_ecs::system::assert_is_system(my_system);
+/// ```
+#[derive(SystemParam)]
+pub struct ConsumableEventPeeker<'w, E: Event> {
+  /// The events to peek at.
+  events: Res<'w, ConsumableEvents<E>>,
+}
+
+impl<'w, E: Event> ConsumableEventPeeker<'w, E> {
+  /// Peeks at the unconsumed events, without consuming them.
+  pub fn peek(&self) -> impl Iterator<Item = &E> {
+    self.events.peek()
+  }
+}
+
 /// An iterator over the unconsumed events.
+///
+/// This also implements [`DoubleEndedIterator`], so events can be consumed
+/// from the back via `.rev()` or `.next_back()`. This is useful e.g. for an
+/// input stack, where the most-recently-sent (topmost) event should be the
+/// first to have a chance to consume a click.
 #[derive(Debug)]
 pub struct ConsumableEventIterator<'w, E: Event> {
   /// The iterator being wrapped.
-  iter: IterMut<'w, Option<E>>,
+  iter: IterMut<'w, EventInstance<E>>,
 }
 
 impl<'w, E: Event> Iterator for ConsumableEventIterator<'w, E> {
   type Item = Consume<'w, E>;
 
   fn next(&mut self) -> Option<Self::Item> {
-    self.iter.find(|event| event.is_some()).map(|event| Consume { event })
+    self
+      .iter
+      .find(|instance| instance.event.is_some())
+      .map(|instance| Consume { id: instance.id, event: &mut instance.event })
   }
 
   fn size_hint(&self) -> (usize, Option<usize>) {
     (0, self.iter.size_hint().1)
   }
+
+  fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+    loop {
+      let instance = self.iter.next()?;
+      if instance.event.is_none() {
+        continue;
+      }
+      if n == 0 {
+        return Some(Consume { id: instance.id, event: &mut instance.event });
+      }
+      n -= 1;
+    }
+  }
+
+  fn last(self) -> Option<Self::Item> {
+    self
+      .iter
+      .rev()
+      .find(|instance| instance.event.is_some())
+      .map(|instance| Consume { id: instance.id, event: &mut instance.event })
+  }
+
+  fn count(self) -> usize {
+    self.iter.filter(|instance| instance.event.is_some()).count()
+  }
+}
+
+impl<'w, E: Event> DoubleEndedIterator for ConsumableEventIterator<'w, E> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    loop {
+      let instance = self.iter.next_back()?;
+      if instance.event.is_some() {
+        return Some(Consume { id: instance.id, event: &mut instance.event });
+      }
+    }
+  }
+}
+
+/// Reads consumable events of type `E`, remembering which events have already
+/// been read so that only newly sent events are yielded.
+///
+/// Unlike [`ConsumableEventReader`], which always scans the whole buffer from
+/// the front, a `ConsumableEventCursor` is useful for systems that read a
+/// persistent event queue (see
+/// [`add_persistent_consumable_event`](crate::ConsumableEventApp::add_persistent_consumable_event))
+/// every frame but only care about events they haven't seen yet.
+///
+/// # Usage
+///
+/// `ConsumableEventCursor`s are usually declared as a [`SystemParam`].
+/// ```
+/// use bevy_ecs::prelude::*;
+/// use bevy_consumable_event::ConsumableEventCursor;
+///
+/// #[derive(Event, Debug)]
+/// pub struct MyEvent; // Custom event type.
+///
+/// fn my_system(mut cursor: ConsumableEventCursor<MyEvent>) {
+///   for mut event in cursor.read_new() {
+///     println!("{:?}", *event);
+///     event.consume();
+///   }
+/// }
+///
+/// bevy_ecs::system::assert_is_system(my_system);
+/// ```
+#[derive(SystemParam)]
+pub struct ConsumableEventCursor<'w, 's, E: Event> {
+  /// The events to read from.
+  events: ResMut<'w, ConsumableEvents<E>>,
+  /// The highest [`EventId`] seen so far by this cursor.
+  last_seen: Local<'s, Option<EventId<E>>>,
+}
+
+impl<'w, 's, E: Event> ConsumableEventCursor<'w, 's, E> {
+  /// Reads the events this cursor has not yet iterated past, skipping ones
+  /// already seen even if they have since been compacted out of the buffer by
+  /// [`ConsumableEvents::clear_consumed`]. On the first call, this is every
+  /// event in the buffer, including ones sent before the cursor was created.
+  pub fn read_new(&mut self) -> ConsumableEventCursorIterator<'_, E> {
+    let events = &mut self.events.events;
+    // Ids increase in buffer order, so the events already seen form a prefix
+    // of the buffer that can be skipped without walking it.
+    let start = match *self.last_seen {
+      Some(seen) => events.partition_point(|instance| instance.id <= seen),
+      None => 0,
+    };
+    ConsumableEventCursorIterator {
+      iter: events[start..].iter_mut(),
+      last_seen: &mut self.last_seen,
+    }
+  }
+}
+
+/// An iterator over the events a [`ConsumableEventCursor`] has not yet seen.
+#[derive(Debug)]
+pub struct ConsumableEventCursorIterator<'w, E: Event> {
+  /// The iterator being wrapped.
+  iter: IterMut<'w, EventInstance<E>>,
+  /// The highest [`EventId`] seen so far, updated as the iterator advances.
+  last_seen: &'w mut Option<EventId<E>>,
+}
+
+impl<'w, E: Event> Iterator for ConsumableEventCursorIterator<'w, E> {
+  type Item = Consume<'w, E>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    for instance in self.iter.by_ref() {
+      if matches!(*self.last_seen, Some(seen) if instance.id <= seen) {
+        continue;
+      }
+      *self.last_seen = Some(instance.id);
+      if instance.event.is_some() {
+        return Some(Consume { id: instance.id, event: &mut instance.event });
+      }
+    }
+    None
+  }
 }
 
 #[cfg(test)]
@@ -303,6 +623,58 @@ mod tests {
     assert_eq!(events.read().count(), 0);
   }
 
+  #[test]
+  fn nth_skips_consumed_events() {
+    let mut events = ConsumableEvents::<TestEvent>::default();
+
+    events.send_batch((0..5).map(|value| TestEvent { value }));
+    events.read().next().unwrap().consume();
+
+    // The consumed event (0) is skipped, so the first remaining event is 1
+    // and the 2nd-after-that remaining event is 3.
+    assert_eq!(events.read().next().unwrap().value, 1);
+    assert_eq!(events.read().nth(2).unwrap().value, 3);
+    assert!(events.read().nth(10).is_none());
+  }
+
+  #[test]
+  fn last_returns_final_unconsumed_event() {
+    let mut events = ConsumableEvents::<TestEvent>::default();
+
+    events.send_batch((0..5).map(|value| TestEvent { value }));
+    events.read().last().unwrap().consume();
+
+    // The last event (4) was consumed above, so the new last is 3.
+    assert_eq!(events.read().last().unwrap().value, 3);
+  }
+
+  #[test]
+  fn count_does_not_consume_events() {
+    let mut events = ConsumableEvents::<TestEvent>::default();
+
+    events.send_batch((0..5).map(|value| TestEvent { value }));
+    events.read().nth(1).unwrap().consume();
+
+    assert_eq!(events.read().count(), 4);
+    assert_eq!(events.read().count(), 4);
+  }
+
+  #[test]
+  fn reverse_iteration_consumes_back_to_front() {
+    let mut events = ConsumableEvents::<TestEvent>::default();
+
+    events.send_batch((0..5).map(|value| TestEvent { value }));
+
+    // Consume from the back: the most-recently-sent events go first, as if
+    // the topmost layer of an input stack were eating clicks.
+    for event in events.read().rev().take(2) {
+      event.consume();
+    }
+
+    let values = events.read().map(|event| event.value).collect::<Vec<_>>();
+    assert_eq!(values, [0, 1, 2]);
+  }
+
   #[test]
   fn clear_consumed_removes_consumed_events() {
     let mut events = ConsumableEvents::<TestEvent>::default();
@@ -325,6 +697,61 @@ mod tests {
     assert_eq!(events.events.len(), 2);
   }
 
+  #[test]
+  fn peek_does_not_consume_events() {
+    let mut events = ConsumableEvents::<TestEvent>::default();
+
+    events.send(TestEvent { value: 1 });
+    events.send(TestEvent { value: 2 });
+
+    let values = events.peek().map(|event| event.value).collect::<Vec<_>>();
+    assert_eq!(values, [1, 2]);
+
+    // Peeking again still sees the same events, since nothing was consumed.
+    let values = events.peek().map(|event| event.value).collect::<Vec<_>>();
+    assert_eq!(values, [1, 2]);
+
+    events.read().next().unwrap().consume();
+
+    let values = events.peek().map(|event| event.value).collect::<Vec<_>>();
+    assert_eq!(values, [2]);
+  }
+
+  #[test]
+  fn read_with_id_yields_ids_in_send_order() {
+    let mut events = ConsumableEvents::<TestEvent>::default();
+
+    events.send(TestEvent { value: 1 });
+    events.send_batch((2..=3).map(|value| TestEvent { value }));
+
+    let (values, ids): (Vec<_>, Vec<_>) = events
+      .read_with_id()
+      .map(|(event, id)| (event.value, id))
+      .unzip();
+    assert_eq!(values, [1, 2, 3]);
+    assert!(ids[0] < ids[1]);
+    assert!(ids[1] < ids[2]);
+  }
+
+  #[test]
+  fn consume_id_matches_read_with_id() {
+    let mut events = ConsumableEvents::<TestEvent>::default();
+
+    events.send(TestEvent { value: 1 });
+
+    let (_, first_id) = events.read_with_id().next().unwrap();
+
+    let mut read = events.read();
+    let event = read.next().unwrap();
+    let id = event.id();
+    assert_eq!(id, first_id);
+    assert_eq!(event.consume().value, 1);
+
+    events.send(TestEvent { value: 2 });
+    let (_, second_id) = events.read_with_id().next().unwrap();
+    assert!(id < second_id);
+  }
+
   #[test]
   fn send_batch() {
     let mut events = ConsumableEvents::<TestEvent>::default();
@@ -365,6 +792,9 @@ mod tests {
         |mut events: ResMut<ConsumableEvents<TestEvent>>| {
           assert_eq!(events.read().count(), 4);
         },
+        |peeker: ConsumableEventPeeker<TestEvent>| {
+          assert_eq!(peeker.peek().count(), 4);
+        },
         |mut events: ConsumableEventReader<TestEvent>| {
           assert_eq!(events.read_and_consume_all().count(), 4);
         },
@@ -377,4 +807,53 @@ mod tests {
     schedule.run(&mut world);
     assert_eq!(world.resource::<ConsumableEvents<TestEvent>>().events.len(), 4);
   }
+
+  #[test]
+  fn cursor_reads_only_new_events() {
+    use bevy_ecs::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct CollectedValues(Vec<usize>);
+
+    fn collect_new(
+      mut cursor: ConsumableEventCursor<TestEvent>,
+      mut collected: ResMut<CollectedValues>,
+    ) {
+      for event in cursor.read_new() {
+        collected.0.push(event.value);
+      }
+    }
+
+    let mut world = World::new();
+    world.init_resource::<ConsumableEvents<TestEvent>>();
+    world.init_resource::<CollectedValues>();
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems(collect_new);
+
+    world
+      .resource_mut::<ConsumableEvents<TestEvent>>()
+      .send_batch((0..3).map(|value| TestEvent { value }));
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<CollectedValues>().0, [0, 1, 2]);
+
+    // Running again with no new events sent yields nothing new.
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<CollectedValues>().0, [0, 1, 2]);
+
+    // Compacting the buffer shifts indices, but the cursor still only picks
+    // up the one genuinely new event.
+    world
+      .resource_mut::<ConsumableEvents<TestEvent>>()
+      .read()
+      .next()
+      .unwrap()
+      .consume();
+    world.resource_mut::<ConsumableEvents<TestEvent>>().clear_consumed();
+    world
+      .resource_mut::<ConsumableEvents<TestEvent>>()
+      .send(TestEvent { value: 3 });
+    schedule.run(&mut world);
+    assert_eq!(world.resource::<CollectedValues>().0, [0, 1, 2, 3]);
+  }
 }